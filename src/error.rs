@@ -12,4 +12,29 @@ pub enum Error {
 
     /// Could not unpack, field contained an unsupported api version
     UnsupportedMagic(Magic),
+
+    /// Could not parse a [`crate::VersionReq`], or the requirement had more
+    /// comparators than [`crate::VersionReq`] has capacity for
+    InvalidVersionReq,
+
+    /// A [`crate::SemverLayout`] did not have exactly 4 fields, its field
+    /// sizes overflowed the target bit width, or its leading (magic) field
+    /// was too narrow; also returned when the buffer given to
+    /// [`crate::Semver::to_custom`]/[`crate::Semver::from_custom`] is
+    /// smaller than the layout's bit width
+    InvalidLayout,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Overflow => write!(f, "field overflowed its bit budget"),
+            Error::UnknownMagic(n) => write!(f, "unknown api version magic: {}", n),
+            Error::UnsupportedMagic(magic) => {
+                write!(f, "unsupported api version magic: {:?}", magic)
+            }
+            Error::InvalidVersionReq => write!(f, "invalid version requirement"),
+            Error::InvalidLayout => write!(f, "invalid semver layout"),
+        }
+    }
 }
@@ -2,16 +2,50 @@ use bitvec::prelude::*;
 
 use crate::{sizes, Error};
 
+/// What to do with a field that doesn't fit in its allotted bit range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// Return [`Error::Overflow`] and leave the bits untouched
+    Error,
+    /// Clamp the value to the range's maximum and store that instead
+    Saturate,
+}
+
 pub fn num_as_bv<const SIZE: usize, const ITER_SIZE: usize>(
     bv: &mut BitArray<[u8; SIZE], Msb0>,
     iter: &mut sizes::SizeIterator<ITER_SIZE>,
     n: u64,
 ) -> Result<(), Error> {
+    num_as_bv_checked(bv, iter, n, Overflow::Error).map(|_| ())
+}
+
+/// Same as [`num_as_bv`], but lets the caller choose what happens on
+/// overflow. Returns whether the stored value was clamped.
+pub fn num_as_bv_checked<const SIZE: usize, const ITER_SIZE: usize>(
+    bv: &mut BitArray<[u8; SIZE], Msb0>,
+    iter: &mut sizes::SizeIterator<ITER_SIZE>,
+    n: u64,
+    overflow: Overflow,
+) -> Result<bool, Error> {
     let range = iter.next().unwrap();
-    if n > 2u64.pow(range.len() as u32) {
-        Err(Error::Overflow)
-    } else {
-        bv[range].store(n);
-        Ok(())
+    let max = 2u64.pow(range.len() as u32) - 1;
+
+    match overflow {
+        Overflow::Error => {
+            if n > max {
+                return Err(Error::Overflow);
+            }
+            bv[range].store(n);
+            Ok(false)
+        }
+        Overflow::Saturate => {
+            if n > max {
+                bv[range].store(max);
+                Ok(true)
+            } else {
+                bv[range].store(n);
+                Ok(false)
+            }
+        }
     }
 }
@@ -4,7 +4,8 @@
 //! * Storage format is not standards-conformant (none exists at the time of writing)
 //! * 32-bit values can represent values in range of `major/minor/patch` = `0 - 1023` (10 bits)
 //! * 64-bit values can represent values in range of `major/minor/patch` = `0 - 65535` (16 bits)
-//! * Other than major/minor/patch features (e.g pre-release) are not supported
+//! * Other than major/minor/patch, only a 128-bit numeric pre-release/build ordinal is
+//!   supported (see [`Semver::to_i128`])
 //!
 //! # Examples
 //!
@@ -100,16 +101,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod prelude {
-    pub use crate::{Error, Magic, Semver};
+    pub use crate::{Error, Magic, Semver, SemverLayout, VersionReq};
 }
 
 mod error;
 mod helpers;
+#[cfg(feature = "serde")]
+mod serde_support;
 mod sizes;
 mod version;
+mod version_req;
 
 pub use error::Error;
+pub use sizes::SemverLayout;
 pub use version::Semver;
+pub use version_req::VersionReq;
 
 /// Magic number - storage format
 ///
@@ -0,0 +1,131 @@
+//! `serde` impls for [`Semver`], gated behind the `serde` feature
+//!
+//! Human-readable formats (e.g. JSON) serialize as the `"major.minor.patch"`
+//! string; compact binary formats (e.g. bincode, postcard) serialize as the
+//! packed `u64` produced by [`Semver::to_u64`], so a stored version occupies
+//! exactly 8 bytes on the wire. [`Semver::pre`] has no slot in that packing
+//! (see [`Semver::to_i128`]), so serializing a `Semver` with `pre: Some(_)`
+//! to a binary format errs with [`Error::Overflow`] instead of silently
+//! dropping it.
+
+use core::fmt;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Error, Semver};
+
+impl Serialize for Semver {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{}.{}.{}", self.major, self.minor, self.patch))
+        } else {
+            if self.pre.is_some() {
+                return Err(serde::ser::Error::custom(Error::Overflow));
+            }
+
+            let packed = self.to_u64().map_err(serde::ser::Error::custom)?;
+            serializer.serialize_u64(packed)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Semver {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SemverVisitor)
+        } else {
+            let packed = u64::deserialize(deserializer)?;
+            Semver::from_u64(packed).map_err(de::Error::custom)
+        }
+    }
+}
+
+struct SemverVisitor;
+
+impl<'de> de::Visitor<'de> for SemverVisitor {
+    type Value = Semver;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a semantic version string \"major.minor.patch\"")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Semver, E>
+    where
+        E: de::Error,
+    {
+        parse_semver(v).map_err(E::custom)
+    }
+}
+
+fn parse_semver(s: &str) -> Result<Semver, &'static str> {
+    let mut parts = s.splitn(3, '.');
+    let major = parts
+        .next()
+        .ok_or("missing major version")?
+        .parse()
+        .map_err(|_| "invalid major version")?;
+    let minor = parts
+        .next()
+        .ok_or("missing minor version")?
+        .parse()
+        .map_err(|_| "invalid minor version")?;
+    let patch = parts
+        .next()
+        .ok_or("missing patch version")?
+        .parse()
+        .map_err(|_| "invalid patch version")?;
+
+    Ok(Semver::new(major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use bitvec::prelude::*;
+
+    use super::*;
+    use crate::helpers::num_as_bv;
+    use crate::{sizes, Magic};
+
+    #[test]
+    fn test_json_roundtrip() {
+        let version = Semver::new(1, 2, 3);
+
+        let json = serde_json::to_string(&version).unwrap();
+        assert_eq!(json, "\"1.2.3\"");
+
+        assert_eq!(serde_json::from_str::<Semver>(&json).unwrap(), version);
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let version = Semver::new(65343, 64000, 65310);
+
+        let bytes = bincode::serialize(&version).unwrap();
+        assert_eq!(bincode::deserialize::<Semver>(&bytes).unwrap(), version);
+    }
+
+    #[test]
+    fn test_binary_rejects_pre() {
+        let mut version = Semver::new(1, 2, 3);
+        version.pre = Some(42);
+
+        assert!(bincode::serialize(&version).is_err());
+    }
+
+    #[test]
+    fn test_binary_rejects_unsupported_magic() {
+        let mut bv: BitArray<[u8; 8], Msb0> = BitArray::ZERO;
+        let mut iter = sizes::size_iterator(&sizes::I64_SIZES);
+        num_as_bv(&mut bv, &mut iter, Magic::V2 as u64).unwrap();
+        let packed = u64::from_le_bytes(bv.data);
+
+        let bytes = bincode::serialize(&packed).unwrap();
+        assert!(bincode::deserialize::<Semver>(&bytes).is_err());
+    }
+}
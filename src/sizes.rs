@@ -1,7 +1,61 @@
 use core::ops::Range;
 
+use crate::Error;
+
 pub const I64_SIZES: [usize; 4] = [4, 16, 16, 16];
 pub const I32_SIZES: [usize; 4] = [2, 10, 10, 10];
+pub const I128_SIZES: [usize; 5] = [4, 32, 32, 32, 28];
+
+/// Minimum width of the leading (magic) field of any layout, wide enough to
+/// hold every [`crate::Magic`] variant (max 4, so 2 bits)
+const MIN_MAGIC_BITS: usize = 2;
+
+/// A custom bit-field layout for packing a `Semver` into `N` fields
+///
+/// `I32_SIZES`/`I64_SIZES` are themselves just two hard-coded instances of
+/// this: `[magic, major, minor, patch]` bit widths summing to at most the
+/// target integer width. Use this to trade bits between fields for a
+/// domain-specific scheme, e.g. `[2, 14, 8, 8]` to give major more room.
+#[derive(Debug)]
+pub struct SemverLayout<const N: usize> {
+    sizes: &'static [usize; N],
+    width: usize,
+}
+
+impl<const N: usize> SemverLayout<N> {
+    /// Validate `sizes` against a target bit width (typically 32 or 64) and
+    /// build a layout from it
+    ///
+    /// Errs if `sizes` doesn't have exactly 4 fields (magic, major, minor,
+    /// patch), if the sizes sum to more than `width` bits, or if the first
+    /// field (the magic) is narrower than [`MIN_MAGIC_BITS`].
+    pub fn new(sizes: &'static [usize; N], width: usize) -> Result<Self, Error> {
+        if N != 4 {
+            return Err(Error::InvalidLayout);
+        }
+
+        let total: usize = sizes.iter().sum();
+        if total > width {
+            return Err(Error::InvalidLayout);
+        }
+
+        if sizes.first().copied().unwrap_or(0) < MIN_MAGIC_BITS {
+            return Err(Error::InvalidLayout);
+        }
+
+        Ok(Self { sizes, width })
+    }
+
+    /// Iterate the bit ranges described by this layout
+    pub fn size_iterator(&self) -> SizeIterator<N> {
+        size_iterator(self.sizes)
+    }
+
+    /// The bit width this layout was validated against
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
 
 pub fn size_iterator<const SIZE: usize>(sizes: &'static [usize; SIZE]) -> SizeIterator<SIZE> {
     SizeIterator {
@@ -47,4 +101,59 @@ mod tests {
         assert_eq!(iterator.next(), Some(36..52));
         assert_eq!(iterator.next(), None);
     }
+
+    #[test]
+    fn test_iterator_128() {
+        let mut iterator = size_iterator(&I128_SIZES);
+        assert_eq!(iterator.next(), Some(0..4));
+        assert_eq!(iterator.next(), Some(4..36));
+        assert_eq!(iterator.next(), Some(36..68));
+        assert_eq!(iterator.next(), Some(68..100));
+        assert_eq!(iterator.next(), Some(100..128));
+        assert_eq!(iterator.next(), None);
+    }
+
+    #[test]
+    fn test_layout_valid() {
+        static SIZES: [usize; 4] = [2, 14, 8, 8];
+        let layout = SemverLayout::new(&SIZES, 32).unwrap();
+        let mut iterator = layout.size_iterator();
+        assert_eq!(iterator.next(), Some(0..2));
+        assert_eq!(iterator.next(), Some(2..16));
+        assert_eq!(iterator.next(), Some(16..24));
+        assert_eq!(iterator.next(), Some(24..32));
+    }
+
+    #[test]
+    fn test_layout_rejects_overflowing_width() {
+        static SIZES: [usize; 4] = [2, 14, 8, 9];
+        assert_eq!(
+            SemverLayout::new(&SIZES, 32).unwrap_err(),
+            Error::InvalidLayout
+        );
+    }
+
+    #[test]
+    fn test_layout_rejects_magic_too_narrow() {
+        static SIZES: [usize; 4] = [1, 10, 10, 10];
+        assert_eq!(
+            SemverLayout::new(&SIZES, 32).unwrap_err(),
+            Error::InvalidLayout
+        );
+    }
+
+    #[test]
+    fn test_layout_rejects_wrong_field_count() {
+        static TOO_FEW: [usize; 2] = [2, 30];
+        assert_eq!(
+            SemverLayout::new(&TOO_FEW, 32).unwrap_err(),
+            Error::InvalidLayout
+        );
+
+        static TOO_MANY: [usize; 5] = [2, 6, 6, 6, 6];
+        assert_eq!(
+            SemverLayout::new(&TOO_MANY, 32).unwrap_err(),
+            Error::InvalidLayout
+        );
+    }
 }
@@ -1,6 +1,10 @@
 use bitvec::prelude::*;
 
-use crate::{helpers::num_as_bv, sizes, Error, Magic};
+use crate::{
+    helpers::{num_as_bv, num_as_bv_checked, Overflow},
+    sizes::{self, SemverLayout},
+    Error, Magic,
+};
 
 /// Represents a version number conforming to the semantic versioning scheme
 ///
@@ -9,10 +13,20 @@ use crate::{helpers::num_as_bv, sizes, Error, Magic};
 ///   and [`Semver::to_u64`]
 /// * From an integer: [`Semver::from_i32`], [`Semver::from_u32`], [`Semver::from_i64`]
 ///   and [`Semver::from_u64`]
+/// * Best-effort, non-erroring conversions: [`Semver::to_i32_saturating`],
+///   [`Semver::to_i32_checked`] and [`Semver::to_i32_overflowing`] (and their
+///   `i64` counterparts)
+/// * 128-bit, with a numeric pre-release/build ordinal: [`Semver::to_i128`],
+///   [`Semver::to_u128`], [`Semver::from_i128`] and [`Semver::from_u128`]
+/// * Sort-stable (big-endian) packing, for use as a sortable key:
+///   [`Semver::to_u32_ordered`], [`Semver::to_u64_ordered`],
+///   [`Semver::from_u32_ordered`] and [`Semver::from_u64_ordered`]
+/// * Custom bit-field layouts: [`Semver::to_custom`] and [`Semver::from_custom`]
+///   against a [`crate::SemverLayout`]
 ///
 /// [Wikipedia](https://en.wikipedia.org/wiki/Software_versioning#Degree_of_compatibility)
 /// explains semantic versioning and the fields in detail.
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct Semver {
     /// The semantic versioning major version (high risk)
     pub major: usize,
@@ -20,6 +34,9 @@ pub struct Semver {
     pub minor: usize,
     /// The semantic versioning patch version (lowest risk)
     pub patch: usize,
+    /// A numeric pre-release/build ordinal, only representable in the
+    /// 128-bit packing (see [`Semver::to_i128`])
+    pub pre: Option<u32>,
 
     /// Magic with what the Semver has been or will be packed
     magic: Magic,
@@ -32,6 +49,7 @@ impl Semver {
             major,
             minor,
             patch,
+            pre: None,
             magic: Default::default(),
         }
     }
@@ -92,6 +110,178 @@ impl Semver {
         Ok(u64::from_le_bytes(val.to_le_bytes()))
     }
 
+    /// Convert to a `u32` whose raw integer value sorts in version
+    /// precedence order (major, then minor, then patch)
+    ///
+    /// Unlike [`Semver::to_u32`], which emits `to_le_bytes` over a
+    /// most-significant-bit-first layout and so does not sort meaningfully,
+    /// this emits `to_be_bytes`, keeping the most significant semantic bits
+    /// in the most significant byte of the integer. The ordered and
+    /// non-ordered encodings are **not** bit-compatible; decode with
+    /// [`Semver::from_u32_ordered`], not [`Semver::from_u32`]
+    pub fn to_u32_ordered(&self) -> Result<u32, Error> {
+        let mut bv: BitArray<[u8; 4], Msb0> = BitArray::ZERO;
+        let sizes = sizes::size_iterator(&sizes::I32_SIZES);
+        self.append_with_size_iterator(&mut bv, sizes)?;
+        Ok(u32::from_be_bytes(bv.data))
+    }
+
+    /// Convert to a `u64` whose raw integer value sorts in version
+    /// precedence order. See [`Semver::to_u32_ordered`] for details
+    pub fn to_u64_ordered(&self) -> Result<u64, Error> {
+        let mut bv: BitArray<[u8; 8], Msb0> = BitArray::ZERO;
+        let sizes = sizes::size_iterator(&sizes::I64_SIZES);
+        self.append_with_size_iterator(&mut bv, sizes)?;
+        Ok(u64::from_be_bytes(bv.data))
+    }
+
+    /// Construct from a `u32` produced by [`Semver::to_u32_ordered`]
+    pub fn from_u32_ordered(n: u32) -> Result<Self, Error> {
+        let bytes = n.to_be_bytes();
+        let bv = bytes.view_bits::<Msb0>();
+        let sizes = sizes::size_iterator(&sizes::I32_SIZES);
+        Self::from_size_iterator(bv, sizes)
+    }
+
+    /// Construct from a `u64` produced by [`Semver::to_u64_ordered`]
+    pub fn from_u64_ordered(n: u64) -> Result<Self, Error> {
+        let bytes = n.to_be_bytes();
+        let bv = bytes.view_bits::<Msb0>();
+        let sizes = sizes::size_iterator(&sizes::I64_SIZES);
+        Self::from_size_iterator(&bv, sizes)
+    }
+
+    /// Convert to an i128, packing `major`/`minor`/`patch` into 32 bits
+    /// each plus a trailing 28-bit numeric pre-release/build ordinal taken
+    /// from [`Semver::pre`]. Errs if any of the fields overflow
+    pub fn to_i128(&self) -> Result<i128, Error> {
+        let mut bv: BitArray<[u8; 16], Msb0> = BitArray::ZERO;
+        let mut sizes = sizes::size_iterator(&sizes::I128_SIZES);
+        let magic = if self.pre.is_some() {
+            Magic::V1
+        } else {
+            Magic::V0
+        };
+        num_as_bv(&mut bv, &mut sizes, magic as u64)?;
+        num_as_bv(&mut bv, &mut sizes, self.major as u64)?;
+        num_as_bv(&mut bv, &mut sizes, self.minor as u64)?;
+        num_as_bv(&mut bv, &mut sizes, self.patch as u64)?;
+        num_as_bv(&mut bv, &mut sizes, self.pre.unwrap_or(0) as u64)?;
+        Ok(i128::from_le_bytes(bv.data))
+    }
+
+    /// Convert to an u128. Errs if any of the fields overflow
+    pub fn to_u128(&self) -> Result<u128, Error> {
+        let val = self.to_i128()?;
+        Ok(u128::from_le_bytes(val.to_le_bytes()))
+    }
+
+    /// Construct from an i128 produced by [`Semver::to_i128`]
+    pub fn from_i128(n: i128) -> Result<Self, Error> {
+        let bytes = n.to_le_bytes();
+        let bv = bytes.view_bits::<Msb0>();
+        let mut sizes = sizes::size_iterator(&sizes::I128_SIZES);
+
+        let magic = convert_api_version_128(bv[sizes.next().unwrap()].load::<u64>())?;
+        let major = bv[sizes.next().unwrap()].load::<usize>();
+        let minor = bv[sizes.next().unwrap()].load::<usize>();
+        let patch = bv[sizes.next().unwrap()].load::<usize>();
+        let pre_field = bv[sizes.next().unwrap()].load::<u32>();
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre: match magic {
+                Magic::V1 => Some(pre_field),
+                _ => None,
+            },
+            magic,
+        })
+    }
+
+    /// Construct from an u128 produced by [`Semver::to_u128`]
+    pub fn from_u128(n: u128) -> Result<Self, Error> {
+        let i128 = i128::from_le_bytes(n.to_le_bytes());
+        Self::from_i128(i128)
+    }
+
+    /// Convert to an i32, clamping any field that overflows its bit budget
+    /// to that field's maximum instead of erring
+    pub fn to_i32_saturating(&self) -> i32 {
+        self.to_i32_overflowing().0
+    }
+
+    /// Convert to an i64, clamping any field that overflows its bit budget
+    /// to that field's maximum instead of erring
+    pub fn to_i64_saturating(&self) -> i64 {
+        self.to_i64_overflowing().0
+    }
+
+    /// Convert to an i32, returning `None` instead of `Err` on overflow
+    pub fn to_i32_checked(&self) -> Option<i32> {
+        self.to_i32().ok()
+    }
+
+    /// Convert to an i64, returning `None` instead of `Err` on overflow
+    pub fn to_i64_checked(&self) -> Option<i64> {
+        self.to_i64().ok()
+    }
+
+    /// Convert to an i32, saturating any overflowing field and reporting
+    /// whether that happened as the second element of the tuple
+    pub fn to_i32_overflowing(&self) -> (i32, bool) {
+        let mut bv: BitArray<[u8; 4], Msb0> = BitArray::ZERO;
+        let sizes = sizes::size_iterator(&sizes::I32_SIZES);
+        let saturated = self.append_with_size_iterator_saturating(&mut bv, sizes);
+        (i32::from_le_bytes(bv.data), saturated)
+    }
+
+    /// Convert to an i64, saturating any overflowing field and reporting
+    /// whether that happened as the second element of the tuple
+    pub fn to_i64_overflowing(&self) -> (i64, bool) {
+        let mut bv: BitArray<[u8; 8], Msb0> = BitArray::ZERO;
+        let sizes = sizes::size_iterator(&sizes::I64_SIZES);
+        let saturated = self.append_with_size_iterator_saturating(&mut bv, sizes);
+        (i64::from_le_bytes(bv.data), saturated)
+    }
+
+    /// Pack against a custom [`SemverLayout`] instead of one of the built-in
+    /// 32/64-bit formats
+    ///
+    /// Errs with [`Error::InvalidLayout`] if `SIZE` (in bytes) is too small
+    /// to hold the layout's validated bit width, or [`Error::Overflow`] if
+    /// any of the fields overflow
+    pub fn to_custom<const N: usize, const SIZE: usize>(
+        &self,
+        layout: &SemverLayout<N>,
+    ) -> Result<[u8; SIZE], Error> {
+        if SIZE * 8 < layout.width() {
+            return Err(Error::InvalidLayout);
+        }
+
+        let mut bv: BitArray<[u8; SIZE], Msb0> = BitArray::ZERO;
+        self.append_with_size_iterator(&mut bv, layout.size_iterator())?;
+        Ok(bv.data)
+    }
+
+    /// Construct from bytes produced by [`Semver::to_custom`] against the
+    /// same [`SemverLayout`]
+    ///
+    /// Errs with [`Error::InvalidLayout`] if `SIZE` (in bytes) is too small
+    /// to hold the layout's validated bit width
+    pub fn from_custom<const N: usize, const SIZE: usize>(
+        bytes: [u8; SIZE],
+        layout: &SemverLayout<N>,
+    ) -> Result<Self, Error> {
+        if SIZE * 8 < layout.width() {
+            return Err(Error::InvalidLayout);
+        }
+
+        let bv = bytes.view_bits::<Msb0>();
+        Self::from_size_iterator(bv, layout.size_iterator())
+    }
+
     fn from_size_iterator<const SIZE: usize>(
         bv: &BitSlice<u8, Msb0>,
         mut sizes: sizes::SizeIterator<SIZE>,
@@ -101,6 +291,7 @@ impl Semver {
             major: bv[sizes.next().unwrap()].load::<usize>(),
             minor: bv[sizes.next().unwrap()].load::<usize>(),
             patch: bv[sizes.next().unwrap()].load::<usize>(),
+            pre: None,
         })
     }
 
@@ -115,6 +306,61 @@ impl Semver {
         num_as_bv(bv, &mut sizes, self.patch as u64)?;
         Ok(())
     }
+
+    /// Like [`Semver::append_with_size_iterator`], but clamps overflowing
+    /// fields instead of erring. Returns whether any field was clamped.
+    fn append_with_size_iterator_saturating<const SIZE: usize, const ITER_SIZE: usize>(
+        &self,
+        bv: &mut BitArray<[u8; SIZE], Msb0>,
+        mut sizes: sizes::SizeIterator<ITER_SIZE>,
+    ) -> bool {
+        // magic always fits in its reserved bits, so overflow can't happen here
+        num_as_bv_checked(bv, &mut sizes, Magic::default() as u64, Overflow::Saturate).unwrap();
+        let major =
+            num_as_bv_checked(bv, &mut sizes, self.major as u64, Overflow::Saturate).unwrap();
+        let minor =
+            num_as_bv_checked(bv, &mut sizes, self.minor as u64, Overflow::Saturate).unwrap();
+        let patch =
+            num_as_bv_checked(bv, &mut sizes, self.patch as u64, Overflow::Saturate).unwrap();
+        major || minor || patch
+    }
+}
+
+impl PartialEq for Semver {
+    /// Compares `major`, `minor`, `patch` and `pre`, deliberately excluding
+    /// the private `magic` field. `magic` only records which packing a
+    /// `Semver` was built from/for, not part of the version it represents,
+    /// and letting it participate here would put `PartialEq` out of sync
+    /// with [`Ord`], which doesn't compare it either
+    fn eq(&self, other: &Self) -> bool {
+        (self.major, self.minor, self.patch, self.pre)
+            == (other.major, other.minor, other.patch, other.pre)
+    }
+}
+
+impl Eq for Semver {}
+
+impl Ord for Semver {
+    /// Compares `major`, then `minor`, then `patch`, then `pre` as a final
+    /// tie-breaker (`None` sorts before any `Some`). `pre` must participate
+    /// here so that `Ord` agrees with [`PartialEq`] — otherwise two versions
+    /// that differ only by `pre` would be unequal but compare as
+    /// `Ordering::Equal`, silently colliding as the same key in a
+    /// `BTreeSet`/`BTreeMap`
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.major, self.minor, self.patch, self.pre).cmp(&(
+            other.major,
+            other.minor,
+            other.patch,
+            other.pre,
+        ))
+    }
+}
+
+impl PartialOrd for Semver {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 fn convert_api_version(n: u64) -> Result<Magic, Error> {
@@ -134,6 +380,25 @@ fn convert_api_version(n: u64) -> Result<Magic, Error> {
     Ok(api_version)
 }
 
+/// Like [`convert_api_version`], but also accepts [`Magic::V1`] (the
+/// pre-release-present layout used by [`Semver::to_i128`])
+fn convert_api_version_128(n: u64) -> Result<Magic, Error> {
+    let api_version = match n {
+        0 => Magic::V0,
+        1 => Magic::V1,
+        2 => Magic::V2,
+        3 => Magic::V3,
+        _ => return Err(Error::UnknownMagic(n)),
+    };
+
+    match api_version {
+        Magic::V0 | Magic::V1 => (),
+        _ => return Err(Error::UnsupportedMagic(api_version)),
+    }
+
+    Ok(api_version)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,35 +487,39 @@ mod tests {
 
     #[test]
     fn test_overflow_i32() {
-        assert!(Semver::new(2usize.pow(10), 2usize.pow(10), 2usize.pow(10))
-            .to_i32()
-            .is_ok());
+        assert!(
+            Semver::new(2usize.pow(10) - 1, 2usize.pow(10) - 1, 2usize.pow(10) - 1)
+                .to_i32()
+                .is_ok()
+        );
 
         // for overflows, see sizes::I32_SIZES
         assert_eq!(
-            Semver::new(2usize.pow(10) + 1, 0, 0).to_i32().unwrap_err(),
+            Semver::new(2usize.pow(10), 0, 0).to_i32().unwrap_err(),
             Error::Overflow
         );
 
         assert_eq!(
-            Semver::new(0, 2usize.pow(10) + 1, 0).to_i32().unwrap_err(),
+            Semver::new(0, 2usize.pow(10), 0).to_i32().unwrap_err(),
             Error::Overflow
         );
 
         assert_eq!(
-            Semver::new(0, 0, 2usize.pow(10) + 1).to_i32().unwrap_err(),
+            Semver::new(0, 0, 2usize.pow(10)).to_i32().unwrap_err(),
             Error::Overflow
         );
     }
 
     #[test]
     fn test_overflow_i64() {
-        assert!(Semver::new(2usize.pow(16), 2usize.pow(16), 2usize.pow(16))
-            .to_i64()
-            .is_ok());
+        assert!(
+            Semver::new(2usize.pow(16) - 1, 2usize.pow(16) - 1, 2usize.pow(16) - 1)
+                .to_i64()
+                .is_ok()
+        );
 
         // see sizes::I64_SIZES
-        let overflow = 2usize.pow(16) + 1;
+        let overflow = 2usize.pow(16);
 
         assert_eq!(
             Semver::new(overflow, 0, 0).to_i64().unwrap_err(),
@@ -268,11 +537,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_i32_saturating() {
+        assert_eq!(
+            Semver::new(2usize.pow(10) - 1, 2usize.pow(10) - 1, 2usize.pow(10) - 1)
+                .to_i32_saturating(),
+            Semver::new(2usize.pow(10) - 1, 2usize.pow(10) - 1, 2usize.pow(10) - 1)
+                .to_i32()
+                .unwrap()
+        );
+
+        let saturated = Semver::new(2usize.pow(10) + 5, 0, 0).to_i32_saturating();
+        assert_eq!(
+            Semver::from_i32(saturated).unwrap(),
+            Semver::new(2usize.pow(10) - 1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_to_i32_checked() {
+        assert!(Semver::new(1, 1, 5).to_i32_checked().is_some());
+        assert_eq!(Semver::new(2usize.pow(10) + 1, 0, 0).to_i32_checked(), None);
+    }
+
+    #[test]
+    fn test_to_i32_overflowing() {
+        let (val, overflowed) = Semver::new(1, 1, 5).to_i32_overflowing();
+        assert_eq!(val, Semver::new(1, 1, 5).to_i32().unwrap());
+        assert!(!overflowed);
+
+        let (_, overflowed) = Semver::new(0, 2usize.pow(10) + 1, 0).to_i32_overflowing();
+        assert!(overflowed);
+    }
+
+    #[test]
+    fn test_to_i64_saturating_checked_overflowing() {
+        assert!(!Semver::new(1, 1, 5).to_i64_overflowing().1);
+
+        let (val, overflowed) = Semver::new(2usize.pow(16) + 1, 0, 0).to_i64_overflowing();
+        assert!(overflowed);
+        assert_eq!(val, Semver::new(2usize.pow(16) + 1, 0, 0).to_i64_saturating());
+
+        assert_eq!(Semver::new(2usize.pow(16) + 1, 0, 0).to_i64_checked(), None);
+    }
+
     fn test_version() -> Semver {
         Semver {
             major: 1,
             minor: 1,
             patch: 5,
+            pre: None,
             magic: Magic::V0,
         }
     }
@@ -292,4 +606,159 @@ mod tests {
     fn assert_roundtrip_u64(ver: Semver) {
         assert_eq!(Semver::from_u64(ver.to_u64().unwrap()).unwrap(), ver);
     }
+
+    #[test]
+    fn test_roundtrip_i128_without_pre() {
+        let ver = Semver::new(1, 0, 20);
+        assert_eq!(Semver::from_i128(ver.to_i128().unwrap()).unwrap(), ver);
+    }
+
+    #[test]
+    fn test_roundtrip_i128_with_pre() {
+        let ver = Semver {
+            pre: Some(7),
+            magic: Magic::V1,
+            ..Semver::new(1, 0, 20)
+        };
+        let packed = ver.to_i128().unwrap();
+        let decoded = Semver::from_i128(packed).unwrap();
+
+        assert_eq!(decoded, ver);
+        assert_eq!(decoded.pre, Some(7));
+    }
+
+    #[test]
+    fn test_roundtrip_u128() {
+        let ver = Semver {
+            pre: Some(42),
+            magic: Magic::V1,
+            ..Semver::new(254, 500, 498)
+        };
+        assert_eq!(Semver::from_u128(ver.to_u128().unwrap()).unwrap(), ver);
+    }
+
+    #[test]
+    fn test_u32_ordered_roundtrip() {
+        let ver = Semver::new(254, 500, 498);
+        let packed = ver.to_u32_ordered().unwrap();
+        assert_eq!(Semver::from_u32_ordered(packed).unwrap(), ver);
+    }
+
+    #[test]
+    fn test_u64_ordered_roundtrip() {
+        let ver = Semver::new(65343, 64000, 65310);
+        let packed = ver.to_u64_ordered().unwrap();
+        assert_eq!(Semver::from_u64_ordered(packed).unwrap(), ver);
+    }
+
+    #[test]
+    fn test_u32_ordered_sorts_by_precedence() {
+        let lower = Semver::new(1, 0, 0).to_u32_ordered().unwrap();
+        let higher = Semver::new(1, 0, 1).to_u32_ordered().unwrap();
+        assert!(lower < higher);
+
+        let lower = Semver::new(1, 9, 9).to_u32_ordered().unwrap();
+        let higher = Semver::new(2, 0, 0).to_u32_ordered().unwrap();
+        assert!(lower < higher);
+    }
+
+    #[test]
+    fn test_u64_ordered_sorts_by_precedence() {
+        let lower = Semver::new(1, 0, 0).to_u64_ordered().unwrap();
+        let higher = Semver::new(1, 1, 0).to_u64_ordered().unwrap();
+        assert!(lower < higher);
+    }
+
+    #[test]
+    fn test_custom_layout_roundtrip() {
+        static SIZES: [usize; 4] = [2, 14, 8, 8];
+        let layout = SemverLayout::new(&SIZES, 32).unwrap();
+
+        let ver = Semver::new(5000, 200, 10);
+        let packed: [u8; 4] = ver.to_custom(&layout).unwrap();
+        assert_eq!(Semver::from_custom(packed, &layout).unwrap(), ver);
+    }
+
+    #[test]
+    fn test_custom_layout_overflow() {
+        static SIZES: [usize; 4] = [2, 14, 8, 8];
+        let layout = SemverLayout::new(&SIZES, 32).unwrap();
+
+        let ver = Semver::new(2usize.pow(14) + 1, 0, 0);
+        assert_eq!(
+            ver.to_custom::<4, 4>(&layout).unwrap_err(),
+            Error::Overflow
+        );
+    }
+
+    #[test]
+    fn test_custom_layout_size_too_small() {
+        static SIZES: [usize; 4] = [2, 14, 8, 8];
+        let layout = SemverLayout::new(&SIZES, 32).unwrap();
+
+        let ver = Semver::new(1, 2, 3);
+        assert_eq!(
+            ver.to_custom::<4, 2>(&layout).unwrap_err(),
+            Error::InvalidLayout
+        );
+        assert_eq!(
+            Semver::from_custom([0u8; 2], &layout).unwrap_err(),
+            Error::InvalidLayout
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Semver::new(1, 0, 0) < Semver::new(1, 0, 1));
+        assert!(Semver::new(1, 0, 0) < Semver::new(1, 1, 0));
+        assert!(Semver::new(1, 0, 0) < Semver::new(2, 0, 0));
+        assert!(Semver::new(1, 9, 9) < Semver::new(2, 0, 0));
+        assert_eq!(Semver::new(1, 2, 3), Semver::new(1, 2, 3));
+    }
+
+    #[test]
+    fn test_ordering_agrees_with_eq_when_pre_differs() {
+        let a = Semver {
+            pre: Some(1),
+            magic: Magic::V1,
+            ..Semver::new(1, 2, 3)
+        };
+        let b = Semver {
+            pre: Some(2),
+            magic: Magic::V1,
+            ..Semver::new(1, 2, 3)
+        };
+
+        assert_ne!(a, b);
+        assert_ne!(a.cmp(&b), core::cmp::Ordering::Equal);
+
+        let mut set = std::collections::BTreeSet::new();
+        set.insert(a);
+        set.insert(b);
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_eq_ignores_magic() {
+        let a = Semver {
+            magic: Magic::V0,
+            ..Semver::new(1, 2, 3)
+        };
+        let b = Semver {
+            magic: Magic::V1,
+            ..Semver::new(1, 2, 3)
+        };
+
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), core::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_i128_overflow() {
+        let ver = Semver {
+            pre: Some(2u32.pow(28)),
+            ..Semver::new(1, 0, 0)
+        };
+        assert_eq!(ver.to_i128().unwrap_err(), Error::Overflow);
+    }
 }
@@ -0,0 +1,265 @@
+use crate::{Error, Semver};
+
+/// Maximum number of AND-combined comparators a [`VersionReq`] can hold
+pub const MAX_COMPARATORS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Comparator {
+    op: Op,
+    major: usize,
+    minor: usize,
+    patch: usize,
+}
+
+impl Comparator {
+    fn matches(&self, v: &Semver) -> bool {
+        let candidate = (v.major, v.minor, v.patch);
+        let bound = (self.major, self.minor, self.patch);
+
+        match self.op {
+            Op::Eq => candidate == bound,
+            Op::Gt => candidate > bound,
+            Op::Ge => candidate >= bound,
+            Op::Lt => candidate < bound,
+            Op::Le => candidate <= bound,
+        }
+    }
+}
+
+/// A version requirement, e.g. `^1.2.3`, `~1.2` or `>=1.0.0, <2.0.0`
+///
+/// Comparators are combined with logical AND: a [`Semver`] matches only if
+/// it satisfies every comparator. Supported syntax:
+/// * `=1.2.3`, `>1.2.3`, `>=1.2.3`, `<1.2.3`, `<=1.2.3`
+/// * caret `^1.2.3` (compatible-within-leftmost-nonzero, e.g. `^1.2.3` is
+///   `>=1.2.3, <2.0.0` but `^0.2.3` is `>=0.2.3, <0.3.0`)
+/// * tilde `~1.2.3` (`>=1.2.3, <1.3.0`) and its partial form `~1.2`
+///
+/// Backed by a fixed-capacity array (max [`MAX_COMPARATORS`]) so it stays
+/// `no_std`-compatible without an allocator.
+#[derive(Debug)]
+pub struct VersionReq {
+    comparators: [Option<Comparator>; MAX_COMPARATORS],
+}
+
+impl VersionReq {
+    /// Parse a version requirement string
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let mut comparators: [Option<Comparator>; MAX_COMPARATORS] = [None; MAX_COMPARATORS];
+        let mut idx = 0;
+
+        for token in s.split(',') {
+            let token = token.trim();
+            if token.is_empty() {
+                continue;
+            }
+
+            let mut parsed: [Option<Comparator>; 2] = [None, None];
+
+            if let Some(rest) = token.strip_prefix('^') {
+                let (major, minor, patch) = parse_triplet(rest)?;
+                parsed[0] = Some(Comparator {
+                    op: Op::Ge,
+                    major,
+                    minor,
+                    patch,
+                });
+                let (umajor, uminor, upatch) = if major > 0 {
+                    (major + 1, 0, 0)
+                } else if minor > 0 {
+                    (0, minor + 1, 0)
+                } else {
+                    (0, 0, patch + 1)
+                };
+                parsed[1] = Some(Comparator {
+                    op: Op::Lt,
+                    major: umajor,
+                    minor: uminor,
+                    patch: upatch,
+                });
+            } else if let Some(rest) = token.strip_prefix('~') {
+                let (major, minor, patch) = parse_partial(rest)?;
+                let minor = minor.ok_or(Error::InvalidVersionReq)?;
+                let patch = patch.unwrap_or(0);
+                parsed[0] = Some(Comparator {
+                    op: Op::Ge,
+                    major,
+                    minor,
+                    patch,
+                });
+                parsed[1] = Some(Comparator {
+                    op: Op::Lt,
+                    major,
+                    minor: minor + 1,
+                    patch: 0,
+                });
+            } else if let Some(rest) = token.strip_prefix(">=") {
+                let (major, minor, patch) = parse_triplet(rest)?;
+                parsed[0] = Some(Comparator {
+                    op: Op::Ge,
+                    major,
+                    minor,
+                    patch,
+                });
+            } else if let Some(rest) = token.strip_prefix("<=") {
+                let (major, minor, patch) = parse_triplet(rest)?;
+                parsed[0] = Some(Comparator {
+                    op: Op::Le,
+                    major,
+                    minor,
+                    patch,
+                });
+            } else if let Some(rest) = token.strip_prefix('>') {
+                let (major, minor, patch) = parse_triplet(rest)?;
+                parsed[0] = Some(Comparator {
+                    op: Op::Gt,
+                    major,
+                    minor,
+                    patch,
+                });
+            } else if let Some(rest) = token.strip_prefix('<') {
+                let (major, minor, patch) = parse_triplet(rest)?;
+                parsed[0] = Some(Comparator {
+                    op: Op::Lt,
+                    major,
+                    minor,
+                    patch,
+                });
+            } else {
+                let rest = token.strip_prefix('=').unwrap_or(token);
+                let (major, minor, patch) = parse_triplet(rest)?;
+                parsed[0] = Some(Comparator {
+                    op: Op::Eq,
+                    major,
+                    minor,
+                    patch,
+                });
+            }
+
+            for comparator in parsed.into_iter().flatten() {
+                if idx >= MAX_COMPARATORS {
+                    return Err(Error::InvalidVersionReq);
+                }
+                comparators[idx] = Some(comparator);
+                idx += 1;
+            }
+        }
+
+        Ok(Self { comparators })
+    }
+
+    /// Returns `true` if `v` satisfies every comparator in this requirement
+    pub fn matches(&self, v: &Semver) -> bool {
+        self.comparators.iter().flatten().all(|c| c.matches(v))
+    }
+}
+
+fn parse_partial(s: &str) -> Result<(usize, Option<usize>, Option<usize>), Error> {
+    let mut parts = s.trim().splitn(3, '.');
+
+    let major = parts
+        .next()
+        .filter(|p| !p.is_empty())
+        .ok_or(Error::InvalidVersionReq)?
+        .parse()
+        .map_err(|_| Error::InvalidVersionReq)?;
+
+    let minor = match parts.next() {
+        Some(p) => Some(p.parse().map_err(|_| Error::InvalidVersionReq)?),
+        None => None,
+    };
+
+    let patch = match parts.next() {
+        Some(p) => Some(p.parse().map_err(|_| Error::InvalidVersionReq)?),
+        None => None,
+    };
+
+    Ok((major, minor, patch))
+}
+
+fn parse_triplet(s: &str) -> Result<(usize, usize, usize), Error> {
+    let (major, minor, patch) = parse_partial(s)?;
+    Ok((
+        major,
+        minor.ok_or(Error::InvalidVersionReq)?,
+        patch.ok_or(Error::InvalidVersionReq)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caret() {
+        let req = VersionReq::parse("^1.2.3").unwrap();
+        assert!(req.matches(&Semver::new(1, 2, 3)));
+        assert!(req.matches(&Semver::new(1, 9, 0)));
+        assert!(!req.matches(&Semver::new(2, 0, 0)));
+        assert!(!req.matches(&Semver::new(1, 2, 2)));
+
+        let req = VersionReq::parse("^0.2.3").unwrap();
+        assert!(req.matches(&Semver::new(0, 2, 3)));
+        assert!(!req.matches(&Semver::new(0, 3, 0)));
+
+        let req = VersionReq::parse("^0.0.3").unwrap();
+        assert!(req.matches(&Semver::new(0, 0, 3)));
+        assert!(!req.matches(&Semver::new(0, 0, 4)));
+    }
+
+    #[test]
+    fn test_tilde() {
+        let req = VersionReq::parse("~1.2.3").unwrap();
+        assert!(req.matches(&Semver::new(1, 2, 3)));
+        assert!(req.matches(&Semver::new(1, 2, 9)));
+        assert!(!req.matches(&Semver::new(1, 3, 0)));
+
+        let req = VersionReq::parse("~1.2").unwrap();
+        assert!(req.matches(&Semver::new(1, 2, 0)));
+        assert!(!req.matches(&Semver::new(1, 3, 0)));
+    }
+
+    #[test]
+    fn test_plain_comparators() {
+        assert!(VersionReq::parse("=1.2.3")
+            .unwrap()
+            .matches(&Semver::new(1, 2, 3)));
+        assert!(VersionReq::parse(">1.2.3")
+            .unwrap()
+            .matches(&Semver::new(1, 2, 4)));
+        assert!(VersionReq::parse(">=1.2.3")
+            .unwrap()
+            .matches(&Semver::new(1, 2, 3)));
+        assert!(VersionReq::parse("<2.0.0")
+            .unwrap()
+            .matches(&Semver::new(1, 9, 9)));
+        assert!(VersionReq::parse("<=1.2.3")
+            .unwrap()
+            .matches(&Semver::new(1, 2, 3)));
+    }
+
+    #[test]
+    fn test_combined_with_and() {
+        let req = VersionReq::parse(">=1.2.3, <2.0.0").unwrap();
+        assert!(req.matches(&Semver::new(1, 9, 9)));
+        assert!(!req.matches(&Semver::new(1, 2, 2)));
+        assert!(!req.matches(&Semver::new(2, 0, 0)));
+    }
+
+    #[test]
+    fn test_invalid_requirement() {
+        assert_eq!(
+            VersionReq::parse("not-a-version").unwrap_err(),
+            Error::InvalidVersionReq
+        );
+    }
+}